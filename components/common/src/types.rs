@@ -1,20 +1,37 @@
 use crate::error::Error;
 use clap::ArgMatches;
+use rand::Rng;
 use std::{collections::HashMap,
+          convert::TryFrom,
           fmt,
-          io,
-          net::{IpAddr,
+          fs,
+          io::{self,
+               BufRead,
+               BufReader,
+               Read,
+               Write},
+          net::{AddrParseError,
+                IpAddr,
                 Ipv4Addr,
+                Ipv6Addr,
                 SocketAddr,
                 SocketAddrV4,
+                SocketAddrV6,
+                TcpStream,
                 ToSocketAddrs},
           num::ParseIntError,
           ops::{Deref,
                 DerefMut},
           option,
+          path::{Path,
+                 PathBuf},
+          process,
           result,
           str::FromStr,
-          time::Duration};
+          sync::mpsc,
+          thread,
+          time::{Duration,
+                 Instant}};
 
 /// Bundles up information about the user and group that a supervised
 /// service should be run as. If the Supervisor itself is running with
@@ -49,6 +66,8 @@ impl Into<HashMap<String, String>> for EventStreamMetadata {
 impl EventStreamMetadata {
     /// The name of the Clap argument we'll use for arguments of this type.
     pub const ARG_NAME: &'static str = "EVENT_STREAM_METADATA";
+    /// The name of the Clap argument for a file containing metadata pairs.
+    pub const FILE_ARG_NAME: &'static str = "EVENT_STREAM_METADATA_FILE";
 
     /// Ensure that user input from Clap can be converted into a
     /// key-value pair we can consume.
@@ -81,14 +100,117 @@ impl EventStreamMetadata {
         Self::split_raw(validated_input).expect("EVENT_STREAM_METADATA should be validated at \
                                                  this point")
     }
+
+    /// Build an instance of `EventStreamMetadata` seeded with well-known host-identity fields
+    /// (hostname, FQDN, Supervisor pid, and Supervisor version), merged with the given
+    /// user-supplied pairs. Values supplied by the user take precedence over the auto-injected
+    /// defaults.
+    pub fn with_host_defaults(user_supplied: HashMap<String, String>) -> Self {
+        let mut meta = Self::host_defaults();
+        meta.extend(user_supplied);
+        Self(meta)
+    }
+
+    /// The well-known host-identity fields that are automatically injected into every
+    /// `EventStreamMetadata`.
+    fn host_defaults() -> HashMap<String, String> {
+        let mut defaults = HashMap::new();
+        if let Ok(hostname) = hostname::get().map(|h| h.to_string_lossy().into_owned()) {
+            defaults.insert("fqdn".to_string(), Self::fqdn(&hostname).unwrap_or_else(|_| {
+                                                     hostname.clone()
+                                                 }));
+            defaults.insert("hostname".to_string(), hostname);
+        }
+        defaults.insert("supervisor_pid".to_string(), process::id().to_string());
+        defaults.insert("supervisor_version".to_string(), env!("CARGO_PKG_VERSION").to_string());
+        defaults
+    }
+
+    /// How long `fqdn` will wait on the system resolver before giving up. This bounds the same
+    /// class of blocking network call that `ConnectConfig` and the proxy handshakes are bounded
+    /// against, so a slow or unreachable DNS server can't hang Supervisor startup indefinitely.
+    const FQDN_RESOLVE_TIMEOUT: Duration = Duration::from_secs(5);
+
+    /// Resolve `hostname`'s fully-qualified domain name via the system resolver
+    /// (`getaddrinfo` with `AI_CANONNAME`), returning an error if the lookup fails, yields no
+    /// canonical name, or doesn't complete within `FQDN_RESOLVE_TIMEOUT`; `host_defaults` then
+    /// falls back to the bare hostname.
+    ///
+    /// This goes through the `dns-lookup` crate rather than calling `libc::getaddrinfo`
+    /// directly, since `libc` doesn't expose the BSD sockets resolver family on Windows; `libc`
+    /// is still used for the portable `AI_CANONNAME` flag value. The lookup itself has no
+    /// built-in timeout, so it's run on a background thread; if the resolver never answers,
+    /// that thread is simply abandoned once the deadline passes.
+    fn fqdn(hostname: &str) -> io::Result<String> {
+        let hostname = hostname.to_string();
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = tx.send(Self::resolve_canonical_name(&hostname));
+        });
+        rx.recv_timeout(Self::FQDN_RESOLVE_TIMEOUT)
+          .unwrap_or_else(|_| {
+              Err(io::Error::other(format!("timed out after {:?} waiting for the resolver",
+                                            Self::FQDN_RESOLVE_TIMEOUT)))
+          })
+    }
+
+    /// Perform the actual (unbounded) `getaddrinfo` lookup; see `fqdn` for the timeout that
+    /// wraps this.
+    fn resolve_canonical_name(hostname: &str) -> io::Result<String> {
+        let hints = dns_lookup::AddrInfoHints { flags: libc::AI_CANONNAME,
+                                                 ..Default::default() };
+        let mut results = dns_lookup::getaddrinfo(Some(hostname), None, Some(hints))?;
+        results.find_map(|res| res.ok().and_then(|info| info.canonname))
+               .ok_or_else(|| {
+                   io::Error::other(format!("resolver returned no canonical name for {}",
+                                             hostname))
+               })
+    }
+
+    /// Ensure that a file given by the user can be parsed into key-value pairs we can consume.
+    #[allow(clippy::needless_pass_by_value)] // Signature required by CLAP
+    pub fn validate_file(path: String) -> result::Result<(), String> {
+        Self::from_file(path).map(|_| ())
+    }
+
+    /// Read a TOML (or JSON) file whose top-level table is a flat string-to-string map of
+    /// metadata pairs, validating each pair with the same non-empty rules as `split_raw`.
+    fn from_file<P: AsRef<Path>>(path: P) -> result::Result<HashMap<String, String>, String> {
+        let raw = fs::read_to_string(path.as_ref()).map_err(|e| e.to_string())?;
+        let parsed: HashMap<String, String> =
+            toml::from_str(&raw).or_else(|_| serde_json::from_str(&raw))
+                                 .map_err(|e| {
+                                     format!("Could not parse {} as TOML or JSON: {}",
+                                             path.as_ref().display(),
+                                             e)
+                                 })?;
+        for (key, value) in &parsed {
+            if key.is_empty() || value.is_empty() {
+                return Err(format!("Invalid key-value pair given (must be a non-empty key and \
+                                     value) in {}: {}={}",
+                                    path.as_ref().display(),
+                                    key,
+                                    value));
+            }
+        }
+        Ok(parsed)
+    }
 }
 
 impl<'a> From<&'a ArgMatches<'a>> for EventStreamMetadata {
-    /// Create an instance of `EventStreamMetadata` from validated
-    /// user input.
+    /// Create an instance of `EventStreamMetadata` from validated user input: the well-known
+    /// host-identity defaults, overridden by pairs loaded from a metadata file, in turn
+    /// overridden by any pairs given directly on the CLI.
     fn from(m: &ArgMatches) -> Self {
+        let mut user_supplied = HashMap::new();
+        if let Some(path) = m.value_of(Self::FILE_ARG_NAME) {
+            user_supplied.extend(Self::from_file(path).expect("EVENT_STREAM_METADATA_FILE \
+                                                                should be validated at this \
+                                                                point"));
+        }
         let raw_meta = m.values_of(Self::ARG_NAME).unwrap_or_default();
-        Self(raw_meta.map(Self::split_validated).collect())
+        user_supplied.extend(raw_meta.map(Self::split_validated));
+        Self::with_host_defaults(user_supplied)
     }
 }
 
@@ -198,6 +320,464 @@ impl Into<Option<Duration>> for EventStreamConnectMethod {
     }
 }
 
+/// Configuration governing how the Supervisor dials the Automate event stream (or any other
+/// `ToSocketAddrs` endpoint): a bounded per-attempt connect timeout, separate from the overall
+/// `EventStreamConnectMethod` deadline, used while falling back across every address a hostname
+/// resolves to.
+#[derive(Clone, Debug)]
+pub struct ConnectConfig {
+    /// The maximum amount of time to spend on any single connection attempt before moving on to
+    /// the next resolved address.
+    pub connect_timeout: Duration,
+}
+
+impl ConnectConfig {
+    /// The name of the Clap argument.
+    pub const ARG_NAME: &'static str = "EVENT_STREAM_CONNECT_ATTEMPT_TIMEOUT";
+    /// The environment variable to set this value.
+    pub const ENVVAR: &'static str = "HAB_EVENT_STREAM_CONNECT_ATTEMPT_TIMEOUT";
+    /// The default per-attempt connect timeout.
+    pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+    pub fn new(connect_timeout: Duration) -> Self { ConnectConfig { connect_timeout } }
+
+    /// Ensure that user input from Clap can be converted into an instance of this type. A value
+    /// of `0` is rejected: unlike `EVENT_STREAM_CONNECT_TIMEOUT`, there's no "immediate" meaning
+    /// for a per-attempt connect timeout, and `TcpStream::connect_timeout` errors out on a zero
+    /// duration, which would otherwise silently break every connection attempt.
+    #[allow(clippy::needless_pass_by_value)] // Signature required by CLAP
+    pub fn validate(value: String) -> result::Result<(), String> {
+        let config = value.parse::<Self>().map_err(|e| e.to_string())?;
+        if config.connect_timeout.is_zero() {
+            return Err(format!("{} must be greater than 0 seconds", Self::ARG_NAME));
+        }
+        Ok(())
+    }
+
+    /// Resolve `addr` to its full set of candidate `SocketAddr`s and try each in turn, bounded
+    /// by `connect_timeout` per attempt, falling back to the next address on error or timeout.
+    /// Succeeds on the first address that connects; fails once every resolved address has been
+    /// tried.
+    pub fn connect<A: ToSocketAddrs>(&self, addr: A) -> io::Result<TcpStream> {
+        let mut last_err = None;
+        for socket_addr in addr.to_socket_addrs()? {
+            match TcpStream::connect_timeout(&socket_addr, self.connect_timeout) {
+                Ok(stream) => return Ok(stream),
+                Err(e) => last_err = Some(e),
+            }
+        }
+        Err(last_err.unwrap_or_else(|| {
+                        io::Error::new(io::ErrorKind::InvalidInput,
+                                        "could not resolve to any addresses")
+                    }))
+    }
+
+    /// Like `connect`, but keeps retrying the full set of resolved addresses until one succeeds,
+    /// `deadline` has passed, or `backoff`'s `max_retries` is exhausted, pacing fast-failing
+    /// attempts (e.g. immediate connection refusals) with `backoff`'s capped exponential
+    /// backoff rather than busy-looping.
+    pub fn connect_until<A>(&self,
+                             addr: A,
+                             deadline: Instant,
+                             backoff: &ReconnectPolicy)
+                             -> io::Result<TcpStream>
+        where A: ToSocketAddrs + Clone
+    {
+        let mut state = ReconnectState::new(*backoff);
+        loop {
+            state.record_attempt();
+            match self.connect(addr.clone()) {
+                Ok(stream) => {
+                    state.record_success();
+                    return Ok(stream);
+                }
+                Err(e) => {
+                    let remaining = match deadline.checked_duration_since(Instant::now()) {
+                        Some(remaining) if !remaining.is_zero() => remaining,
+                        _ => return Err(e),
+                    };
+                    match state.record_failure() {
+                        Some(delay) => thread::sleep(delay.min(remaining)),
+                        None => return Err(e),
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Default for ConnectConfig {
+    fn default() -> Self { ConnectConfig::new(Self::DEFAULT_CONNECT_TIMEOUT) }
+}
+
+impl FromStr for ConnectConfig {
+    type Err = ParseIntError;
+
+    /// Parses a number of seconds for the per-attempt connect timeout.
+    fn from_str(s: &str) -> result::Result<Self, Self::Err> {
+        let secs = s.parse()?;
+        Ok(ConnectConfig::new(Duration::from_secs(secs)))
+    }
+}
+
+impl<'a> From<&'a ArgMatches<'a>> for ConnectConfig {
+    /// Create an instance of `ConnectConfig` from validated user input, falling back to the
+    /// default per-attempt connect timeout if the user didn't set one.
+    fn from(m: &ArgMatches) -> Self {
+        m.value_of(Self::ARG_NAME)
+         .map(|s| {
+             s.parse()
+              .expect("EVENT_STREAM_CONNECT_ATTEMPT_TIMEOUT should be validated at this point")
+         })
+         .unwrap_or_default()
+    }
+}
+
+/// An outbound proxy through which the event-stream connection to Automate should be dialed,
+/// for supervisors whose network only permits egress through a proxy.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EventStreamProxyConfig {
+    /// A SOCKS5 proxy, addressed as `host:port`.
+    Socks5 { addr: String },
+    /// An HTTP CONNECT proxy, addressed as `host:port`.
+    Http { addr: String },
+}
+
+impl EventStreamProxyConfig {
+    /// The name of the Clap argument.
+    pub const ARG_NAME: &'static str = "EVENT_STREAM_PROXY";
+    /// The environment variable to set this value.
+    pub const ENVVAR: &'static str = "HAB_EVENT_STREAM_PROXY";
+
+    /// Ensure that user input from Clap can be converted into an instance of this type.
+    #[allow(clippy::needless_pass_by_value)] // Signature required by CLAP
+    pub fn validate(value: String) -> result::Result<(), String> {
+        value.parse::<Self>().map(|_| ())
+    }
+
+    /// Connect to the configured proxy and complete its handshake so that the returned stream
+    /// is tunnelled to `dest_host:dest_port`, ready to hand off to the event-stream transport.
+    /// `connect_config`'s `connect_timeout` bounds both the dial to the proxy and every read of
+    /// its handshake response, so a stalled or malicious proxy can't hang startup indefinitely.
+    pub fn connect(&self,
+                   connect_config: &ConnectConfig,
+                   dest_host: &str,
+                   dest_port: u16)
+                   -> io::Result<TcpStream> {
+        match self {
+            EventStreamProxyConfig::Socks5 { addr } => {
+                Self::socks5_connect(connect_config, addr, dest_host, dest_port)
+            }
+            EventStreamProxyConfig::Http { addr } => {
+                Self::http_connect(connect_config, addr, dest_host, dest_port)
+            }
+        }
+    }
+
+    /// Performs the SOCKS5 greeting (advertising the no-auth method) followed by a CONNECT
+    /// command addressed to `dest_host:dest_port` by domain name.
+    fn socks5_connect(connect_config: &ConnectConfig,
+                       proxy_addr: &str,
+                       dest_host: &str,
+                       dest_port: u16)
+                       -> io::Result<TcpStream> {
+        let dest_host_len = u8::try_from(dest_host.len()).map_err(|_| {
+                                 io::Error::other(format!("SOCKS5 destination hostname is too \
+                                                           long to encode (must be at most 255 \
+                                                           bytes): {}",
+                                                          dest_host))
+                             })?;
+
+        let mut stream = connect_config.connect(proxy_addr)?;
+        Self::set_handshake_timeouts(&stream, connect_config)?;
+
+        stream.write_all(&[0x05, 0x01, 0x00])?;
+        let mut greeting_reply = [0u8; 2];
+        stream.read_exact(&mut greeting_reply)?;
+        if greeting_reply != [0x05, 0x00] {
+            return Err(io::Error::other("SOCKS5 proxy rejected the no-auth method"));
+        }
+
+        let mut request = vec![0x05, 0x01, 0x00, 0x03, dest_host_len];
+        request.extend_from_slice(dest_host.as_bytes());
+        request.extend_from_slice(&dest_port.to_be_bytes());
+        stream.write_all(&request)?;
+
+        let mut reply_header = [0u8; 4];
+        stream.read_exact(&mut reply_header)?;
+        if reply_header[1] != 0x00 {
+            return Err(io::Error::other(format!("SOCKS5 CONNECT failed with status {}",
+                                                 reply_header[1])));
+        }
+        // Drain the bound address the proxy reports back, whose length depends on its type.
+        match reply_header[3] {
+            0x01 => drain(&mut stream, 4 + 2)?,  // IPv4 + port
+            0x03 => {
+                let mut len = [0u8; 1];
+                stream.read_exact(&mut len)?;
+                drain(&mut stream, len[0] as usize + 2)?; // domain + port
+            }
+            0x04 => drain(&mut stream, 16 + 2)?, // IPv6 + port
+            other => {
+                return Err(io::Error::other(format!("SOCKS5 proxy returned an unknown address \
+                                                      type: {}",
+                                                     other)));
+            }
+        }
+        Self::clear_handshake_timeouts(&stream)?;
+        Ok(stream)
+    }
+
+    /// Issues a `CONNECT host:port HTTP/1.1` request and requires a `200` response before
+    /// handing the stream off to the caller.
+    fn http_connect(connect_config: &ConnectConfig,
+                     proxy_addr: &str,
+                     dest_host: &str,
+                     dest_port: u16)
+                     -> io::Result<TcpStream> {
+        let stream = connect_config.connect(proxy_addr)?;
+        Self::set_handshake_timeouts(&stream, connect_config)?;
+
+        let mut writer = stream.try_clone()?;
+        writer.write_all(format!("CONNECT {host}:{port} HTTP/1.1\r\nHost: {host}:{port}\r\n\r\n",
+                                  host = dest_host,
+                                  port = dest_port).as_bytes())?;
+
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let mut status_line = String::new();
+        reader.read_line(&mut status_line)?;
+        if !status_line.contains(" 200 ") {
+            return Err(io::Error::other(format!("HTTP proxy CONNECT failed: {}",
+                                                 status_line.trim())));
+        }
+        let mut line = String::new();
+        loop {
+            line.clear();
+            reader.read_line(&mut line)?;
+            if line == "\r\n" || line.is_empty() {
+                break;
+            }
+        }
+        Self::clear_handshake_timeouts(&stream)?;
+        Ok(stream)
+    }
+
+    /// Bound every read/write of the handshake by `connect_config`'s connect timeout, so a
+    /// stalled or malicious proxy can't hang the handshake indefinitely.
+    fn set_handshake_timeouts(stream: &TcpStream, connect_config: &ConnectConfig) -> io::Result<()> {
+        stream.set_read_timeout(Some(connect_config.connect_timeout))?;
+        stream.set_write_timeout(Some(connect_config.connect_timeout))
+    }
+
+    /// Undo `set_handshake_timeouts` once the handshake has completed, so the long-lived,
+    /// mostly-idle event-stream connection isn't left with a short read/write timeout that
+    /// would spuriously fire the first time the link goes quiet.
+    fn clear_handshake_timeouts(stream: &TcpStream) -> io::Result<()> {
+        stream.set_read_timeout(None)?;
+        stream.set_write_timeout(None)
+    }
+}
+
+/// Reads and discards exactly `len` bytes from `stream`.
+fn drain<R: Read>(stream: &mut R, len: usize) -> io::Result<()> {
+    let mut buf = vec![0u8; len];
+    stream.read_exact(&mut buf)
+}
+
+impl FromStr for EventStreamProxyConfig {
+    type Err = String;
+
+    /// Parses a `socks5://host:port` or `http://host:port` proxy address.
+    fn from_str(s: &str) -> result::Result<Self, Self::Err> {
+        if let Some(addr) = s.strip_prefix("socks5://") {
+            Ok(EventStreamProxyConfig::Socks5 { addr: addr.to_string() })
+        } else if let Some(addr) = s.strip_prefix("http://") {
+            Ok(EventStreamProxyConfig::Http { addr: addr.to_string() })
+        } else {
+            Err(format!("Invalid event stream proxy (must be of the form 'socks5://host:port' \
+                         or 'http://host:port'): {}",
+                        s))
+        }
+    }
+}
+
+impl<'a> From<&'a ArgMatches<'a>> for EventStreamProxyConfig {
+    /// Create an instance of `EventStreamProxyConfig` from validated user input.
+    fn from(m: &ArgMatches) -> Self {
+        m.value_of(Self::ARG_NAME)
+         .expect("EVENT_STREAM_PROXY should be set")
+         .parse()
+         .expect("EVENT_STREAM_PROXY should be validated at this point")
+    }
+}
+
+/// The connection state of the event stream link to Automate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConnectionState {
+    /// Not currently connected, and not attempting to connect.
+    Disconnected,
+    /// A connection attempt is in flight.
+    Connecting,
+    /// Currently connected.
+    Connected,
+}
+
+/// Capped exponential backoff parameters for reconnecting the event stream after a mid-run
+/// disconnect.
+#[derive(Clone, Copy, Debug)]
+pub struct ReconnectPolicy {
+    /// The delay before the first reconnect attempt.
+    pub base_delay: Duration,
+    /// The maximum delay between reconnect attempts, regardless of how many have failed.
+    pub max_delay: Duration,
+    /// The maximum number of consecutive failures to retry before giving up, or `None` to
+    /// retry forever.
+    pub max_retries: Option<u32>,
+    /// Whether to add random jitter to each computed delay, to avoid a thundering herd of
+    /// supervisors reconnecting in lockstep.
+    pub jitter: bool,
+}
+
+impl ReconnectPolicy {
+    /// The name of the Clap argument for the base reconnect delay.
+    pub const BASE_DELAY_ARG_NAME: &'static str = "EVENT_STREAM_RECONNECT_BASE_DELAY";
+    /// The environment variable to set the base reconnect delay.
+    pub const BASE_DELAY_ENVVAR: &'static str = "HAB_EVENT_STREAM_RECONNECT_BASE_DELAY";
+    /// The name of the Clap argument for the max reconnect delay.
+    pub const MAX_DELAY_ARG_NAME: &'static str = "EVENT_STREAM_RECONNECT_MAX_DELAY";
+    /// The environment variable to set the max reconnect delay.
+    pub const MAX_DELAY_ENVVAR: &'static str = "HAB_EVENT_STREAM_RECONNECT_MAX_DELAY";
+    /// The name of the Clap argument for the max number of reconnect retries.
+    pub const MAX_RETRIES_ARG_NAME: &'static str = "EVENT_STREAM_RECONNECT_MAX_RETRIES";
+    /// The environment variable to set the max number of reconnect retries.
+    pub const MAX_RETRIES_ENVVAR: &'static str = "HAB_EVENT_STREAM_RECONNECT_MAX_RETRIES";
+
+    /// Ensure that user input from Clap for the base/max reconnect delay (a number of seconds)
+    /// can be converted into a `Duration`.
+    #[allow(clippy::needless_pass_by_value)] // Signature required by CLAP
+    pub fn validate_delay_secs(value: String) -> result::Result<(), String> {
+        value.parse::<u64>().map(|_| ()).map_err(|e| e.to_string())
+    }
+
+    /// Ensure that user input from Clap for the max number of reconnect retries can be
+    /// converted into a `u32`.
+    #[allow(clippy::needless_pass_by_value)] // Signature required by CLAP
+    pub fn validate_max_retries(value: String) -> result::Result<(), String> {
+        value.parse::<u32>().map(|_| ()).map_err(|e| e.to_string())
+    }
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        ReconnectPolicy { base_delay: Duration::from_secs(1),
+                          max_delay: Duration::from_secs(60),
+                          max_retries: None,
+                          jitter: true }
+    }
+}
+
+impl<'a> From<&'a ArgMatches<'a>> for ReconnectPolicy {
+    /// Create an instance of `ReconnectPolicy` from validated user input, falling back to the
+    /// default for any knob the user didn't set.
+    fn from(m: &ArgMatches) -> Self {
+        let default = Self::default();
+
+        let base_delay =
+            m.value_of(Self::BASE_DELAY_ARG_NAME)
+             .map(|s| {
+                 Duration::from_secs(s.parse().expect("EVENT_STREAM_RECONNECT_BASE_DELAY \
+                                                        should be validated at this point"))
+             })
+             .unwrap_or(default.base_delay);
+        let max_delay =
+            m.value_of(Self::MAX_DELAY_ARG_NAME)
+             .map(|s| {
+                 Duration::from_secs(s.parse().expect("EVENT_STREAM_RECONNECT_MAX_DELAY should \
+                                                        be validated at this point"))
+             })
+             .unwrap_or(default.max_delay);
+        let max_retries =
+            m.value_of(Self::MAX_RETRIES_ARG_NAME)
+             .map(|s| {
+                 s.parse().expect("EVENT_STREAM_RECONNECT_MAX_RETRIES should be validated at \
+                                   this point")
+             })
+             .or(default.max_retries);
+
+        ReconnectPolicy { base_delay,
+                          max_delay,
+                          max_retries,
+                          jitter: default.jitter }
+    }
+}
+
+/// Tracks the current connection state of the event stream and drives reconnection with capped
+/// exponential backoff.
+#[derive(Clone, Debug)]
+pub struct ReconnectState {
+    state:                ConnectionState,
+    last_attempt:         Option<Instant>,
+    consecutive_failures: u32,
+    policy:               ReconnectPolicy,
+}
+
+impl ReconnectState {
+    pub fn new(policy: ReconnectPolicy) -> Self {
+        ReconnectState { state: ConnectionState::Disconnected,
+                         last_attempt: None,
+                         consecutive_failures: 0,
+                         policy }
+    }
+
+    pub fn state(&self) -> ConnectionState { self.state }
+
+    pub fn last_attempt(&self) -> Option<Instant> { self.last_attempt }
+
+    /// Record the start of a new connection attempt.
+    pub fn record_attempt(&mut self) {
+        self.state = ConnectionState::Connecting;
+        self.last_attempt = Some(Instant::now());
+    }
+
+    /// Record a successful connection; resets the backoff to its base delay.
+    pub fn record_success(&mut self) {
+        self.state = ConnectionState::Connected;
+        self.consecutive_failures = 0;
+    }
+
+    /// Record a failed connection attempt, returning the delay to wait before retrying, or
+    /// `None` if `max_retries` consecutive failures have already been observed.
+    pub fn record_failure(&mut self) -> Option<Duration> {
+        self.state = ConnectionState::Disconnected;
+        if let Some(max_retries) = self.policy.max_retries {
+            if self.consecutive_failures >= max_retries {
+                return None;
+            }
+        }
+        let delay = self.next_delay();
+        self.consecutive_failures += 1;
+        Some(delay)
+    }
+
+    /// The delay for the next reconnect attempt: the base delay doubled once per consecutive
+    /// failure, capped at `max_delay`, with optional jitter added.
+    fn next_delay(&self) -> Duration {
+        let shift = self.consecutive_failures.min(16);
+        let exp = self.policy
+                      .base_delay
+                      .saturating_mul(1u32.checked_shl(shift).unwrap_or(u32::MAX));
+        let delay = exp.min(self.policy.max_delay);
+        if self.policy.jitter {
+            let jitter_ceiling = (delay.as_millis() as u64 / 10).max(1);
+            let jitter_ms = rand::thread_rng().gen_range(0..=jitter_ceiling);
+            delay.saturating_add(Duration::from_millis(jitter_ms))
+                 .min(self.policy.max_delay)
+        } else {
+            delay
+        }
+    }
+}
+
 habitat_core::env_config_socketaddr!(#[derive(Clone, Copy, PartialEq, Eq, Debug)]
                                      pub GossipListenAddr,
                                      HAB_LISTEN_GOSSIP,
@@ -206,27 +786,53 @@ habitat_core::env_config_socketaddr!(#[derive(Clone, Copy, PartialEq, Eq, Debug)
 impl GossipListenAddr {
     pub const DEFAULT_PORT: u16 = 9638;
 
-    /// When local gossip mode is used we still startup the gossip layer but set
-    /// it to listen on 127.0.0.2 to scope it to the local node but ignore connections from
-    /// 127.0.0.1. This turns out to be much simpler than the cascade of changes that would
-    /// be involved in not setting up a gossip listener at all.
-    pub fn local_only() -> Self {
-        GossipListenAddr(SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::new(127, 0, 0, 2),
-                                                          Self::DEFAULT_PORT)))
+    /// A port, distinct from `DEFAULT_PORT`, used to scope an IPv6 local-only gossip listener.
+    /// Unlike IPv4's entire `127.0.0.0/8` block, only `::1` is guaranteed to be a usable
+    /// loopback address without additional interface configuration, so IPv6 distinguishes the
+    /// local-only listener by port rather than by address.
+    const LOCAL_ONLY_IPV6_PORT: u16 = Self::DEFAULT_PORT + 1;
+
+    /// When local gossip mode is used we still startup the gossip layer but set it to listen on
+    /// a loopback address scoped to the local node but distinct from the regular gossip
+    /// listener, so it ignores connections arriving over the normal loopback path. This turns
+    /// out to be much simpler than the cascade of changes that would be involved in not setting
+    /// up a gossip listener at all.
+    ///
+    /// The address family (IPv4 or IPv6) used matches that of `self`. For IPv4, this is
+    /// `127.0.0.2`, a distinct address within the loopback block; for IPv6, where `::1` is the
+    /// only loopback address guaranteed to be usable without extra interface configuration, this
+    /// is `::1` on a distinct, dedicated port instead.
+    pub fn local_only(&self) -> Self {
+        match self.0 {
+            SocketAddr::V4(_) => {
+                GossipListenAddr(SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::new(127, 0, 0, 2),
+                                                                  Self::DEFAULT_PORT)))
+            }
+            SocketAddr::V6(_) => {
+                GossipListenAddr(SocketAddr::V6(SocketAddrV6::new(Ipv6Addr::LOCALHOST,
+                                                                  Self::LOCAL_ONLY_IPV6_PORT,
+                                                                  0,
+                                                                  0)))
+            }
+        }
     }
 
     /// Generate an address at which a server configured with this
     /// GossipListenAddr can communicate with itself.
     ///
-    /// In particular, a server configured to listen on `0.0.0.0` vs
-    /// `192.168.1.1` should be contacted via `127.0.0.1` in the
-    /// former case, but `192.168.1.1` in the latter.
+    /// In particular, a server configured to listen on `0.0.0.0`/`::` vs
+    /// `192.168.1.1`/`2001:db8::1` should be contacted via `127.0.0.1`/`::1` in the
+    /// former case, but `192.168.1.1`/`2001:db8::1` in the latter.
     pub fn local_addr(&self) -> Self {
         let mut addr = *self;
-        if addr.0.ip().is_unspecified() {
-            // TODO (CM): Use Ipv4Addr::loopback() when it's no longer experimental
-            // TODO (CM): Support IPV6, once we do that more broadly
-            addr.0.set_ip(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)));
+        match addr.0 {
+            SocketAddr::V4(v4) if v4.ip().is_unspecified() => {
+                addr.0.set_ip(IpAddr::V4(Ipv4Addr::LOCALHOST));
+            }
+            SocketAddr::V6(v6) if v6.ip().is_unspecified() => {
+                addr.0.set_ip(IpAddr::V6(Ipv6Addr::LOCALHOST));
+            }
+            _ => {}
         }
         addr
     }
@@ -272,31 +878,307 @@ impl ToSocketAddrs for HttpListenAddr {
     fn to_socket_addrs(&self) -> io::Result<Self::Iter> { self.0.to_socket_addrs() }
 }
 
-habitat_core::env_config_socketaddr!(#[derive(Clone, Copy, PartialEq, Eq, Debug)]
-                                     pub ListenCtlAddr,
-                                     HAB_LISTEN_CTL,
-                                     Ipv4Addr::LOCALHOST, Self::DEFAULT_PORT);
+/// The listen address for the Supervisor's control gateway.
+///
+/// This is usually a TCP socket address, but may instead be the path to a Unix domain socket,
+/// which avoids port allocation entirely and lets operators lock down access with filesystem
+/// permissions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ListenCtlAddr {
+    /// Listen on a TCP socket address.
+    Tcp(SocketAddr),
+    /// Listen on a Unix domain socket at the given path.
+    Unix(PathBuf),
+}
 
 impl ListenCtlAddr {
     pub const DEFAULT_PORT: u16 = 9632;
+    /// The name of the environment variable used to configure this value.
+    pub const ENVVAR: &'static str = "HAB_LISTEN_CTL";
 
     pub fn new(ip: Ipv4Addr, port: u16) -> Self {
-        ListenCtlAddr(SocketAddr::V4(SocketAddrV4::new(ip, port)))
+        ListenCtlAddr::Tcp(SocketAddr::V4(SocketAddrV4::new(ip, port)))
+    }
+
+    /// The IP address of this listener, if it is a TCP address.
+    pub fn ip(&self) -> Option<IpAddr> {
+        match self {
+            ListenCtlAddr::Tcp(addr) => Some(addr.ip()),
+            ListenCtlAddr::Unix(_) => None,
+        }
+    }
+
+    /// The port of this listener, if it is a TCP address.
+    pub fn port(&self) -> Option<u16> {
+        match self {
+            ListenCtlAddr::Tcp(addr) => Some(addr.port()),
+            ListenCtlAddr::Unix(_) => None,
+        }
+    }
+
+    /// This listener as a `SocketAddr`, if it is a TCP address.
+    pub fn as_tcp(&self) -> Option<&SocketAddr> {
+        match self {
+            ListenCtlAddr::Tcp(addr) => Some(addr),
+            ListenCtlAddr::Unix(_) => None,
+        }
     }
+}
+
+impl Default for ListenCtlAddr {
+    fn default() -> Self { ListenCtlAddr::new(Ipv4Addr::LOCALHOST, Self::DEFAULT_PORT) }
+}
 
-    pub fn ip(&self) -> IpAddr { self.0.ip() }
+impl FromStr for ListenCtlAddr {
+    type Err = AddrParseError;
 
-    pub fn port(&self) -> u16 { self.0.port() }
+    /// Parses either a `host:port` TCP address, or a `unix:/path/to/socket` Unix domain socket
+    /// path.
+    fn from_str(s: &str) -> result::Result<Self, Self::Err> {
+        match s.strip_prefix("unix:") {
+            Some(path) => Ok(ListenCtlAddr::Unix(PathBuf::from(path))),
+            None => s.parse().map(ListenCtlAddr::Tcp),
+        }
+    }
 }
 
-impl AsRef<SocketAddr> for ListenCtlAddr {
-    fn as_ref(&self) -> &SocketAddr { &self.0 }
+impl fmt::Display for ListenCtlAddr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ListenCtlAddr::Tcp(addr) => write!(f, "{}", addr),
+            ListenCtlAddr::Unix(path) => write!(f, "unix:{}", path.display()),
+        }
+    }
+}
+
+impl habitat_core::env::Config for ListenCtlAddr {
+    const ENVVAR: &'static str = Self::ENVVAR;
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    mod event_stream_metadata {
+        use super::*;
+
+        #[test]
+        fn with_host_defaults_injects_well_known_keys() {
+            let meta: HashMap<String, String> =
+                EventStreamMetadata::with_host_defaults(HashMap::new()).into();
+            assert!(meta.contains_key("hostname"));
+            assert!(meta.contains_key("fqdn"));
+            assert!(meta.contains_key("supervisor_pid"));
+            assert!(meta.contains_key("supervisor_version"));
+        }
+
+        #[test]
+        fn user_supplied_values_take_precedence_over_defaults() {
+            let mut user_supplied = HashMap::new();
+            user_supplied.insert("supervisor_pid".to_string(), "overridden".to_string());
+            let meta: HashMap<String, String> =
+                EventStreamMetadata::with_host_defaults(user_supplied).into();
+            assert_eq!(meta.get("supervisor_pid"), Some(&"overridden".to_string()));
+        }
+
+        #[test]
+        fn from_file_parses_a_flat_toml_table() {
+            let dir = std::env::temp_dir();
+            let path = dir.join("habitat_event_stream_metadata_test.toml");
+            fs::write(&path, "region = \"us-west-2\"\nteam = \"release\"\n").unwrap();
+            let parsed = EventStreamMetadata::from_file(&path).unwrap();
+            assert_eq!(parsed.get("region"), Some(&"us-west-2".to_string()));
+            assert_eq!(parsed.get("team"), Some(&"release".to_string()));
+            fs::remove_file(&path).unwrap();
+        }
+
+        #[test]
+        fn from_file_rejects_empty_values() {
+            let dir = std::env::temp_dir();
+            let path = dir.join("habitat_event_stream_metadata_test_invalid.toml");
+            fs::write(&path, "region = \"\"\n").unwrap();
+            assert!(EventStreamMetadata::from_file(&path).is_err());
+            fs::remove_file(&path).unwrap();
+        }
+    }
+
+    mod event_stream_proxy_config {
+        use super::*;
+        use std::net::TcpListener;
+
+        #[test]
+        fn parses_socks5_proxy() {
+            let proxy: EventStreamProxyConfig = "socks5://10.0.0.1:1080".parse().unwrap();
+            assert_eq!(proxy, EventStreamProxyConfig::Socks5 { addr: "10.0.0.1:1080".to_string() });
+        }
+
+        #[test]
+        fn parses_http_proxy() {
+            let proxy: EventStreamProxyConfig = "http://10.0.0.1:3128".parse().unwrap();
+            assert_eq!(proxy, EventStreamProxyConfig::Http { addr: "10.0.0.1:3128".to_string() });
+        }
+
+        #[test]
+        fn rejects_unknown_scheme() {
+            assert!("ftp://10.0.0.1:21".parse::<EventStreamProxyConfig>().is_err());
+        }
+
+        #[test]
+        fn socks5_connect_rejects_a_hostname_over_255_bytes() {
+            let connect_config = ConnectConfig::default();
+            let dest_host = "a".repeat(256);
+            let result = EventStreamProxyConfig::socks5_connect(&connect_config,
+                                                                 "127.0.0.1:1",
+                                                                 &dest_host,
+                                                                 443);
+            assert!(result.is_err());
+        }
+
+        #[test]
+        fn socks5_connect_clears_handshake_timeouts_once_tunnelled() {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let proxy_addr = listener.local_addr().unwrap();
+            let server = thread::spawn(move || {
+                let (mut conn, _) = listener.accept().unwrap();
+
+                let mut greeting = [0u8; 3];
+                conn.read_exact(&mut greeting).unwrap();
+                conn.write_all(&[0x05, 0x00]).unwrap();
+
+                let mut header = [0u8; 5];
+                conn.read_exact(&mut header).unwrap();
+                let domain_len = header[4] as usize;
+                let mut rest = vec![0u8; domain_len + 2];
+                conn.read_exact(&mut rest).unwrap();
+
+                conn.write_all(&[0x05, 0x00, 0x00, 0x01, 0, 0, 0, 0, 0, 0]).unwrap();
+            });
+
+            let connect_config = ConnectConfig::new(Duration::from_secs(1));
+            let stream = EventStreamProxyConfig::socks5_connect(&connect_config,
+                                                                 &proxy_addr.to_string(),
+                                                                 "example.com",
+                                                                 443).unwrap();
+            assert_eq!(stream.read_timeout().unwrap(), None);
+            assert_eq!(stream.write_timeout().unwrap(), None);
+            server.join().unwrap();
+        }
+
+        #[test]
+        fn http_connect_clears_handshake_timeouts_once_tunnelled() {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let proxy_addr = listener.local_addr().unwrap();
+            let server = thread::spawn(move || {
+                let (mut conn, _) = listener.accept().unwrap();
+
+                let mut reader = BufReader::new(conn.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                assert!(request_line.starts_with("CONNECT example.com:443 HTTP/1.1"));
+                let mut line = String::new();
+                loop {
+                    line.clear();
+                    reader.read_line(&mut line).unwrap();
+                    if line == "\r\n" || line.is_empty() {
+                        break;
+                    }
+                }
+
+                conn.write_all(b"HTTP/1.1 200 Connection established\r\n\r\n").unwrap();
+            });
+
+            let connect_config = ConnectConfig::new(Duration::from_secs(1));
+            let stream = EventStreamProxyConfig::http_connect(&connect_config,
+                                                                &proxy_addr.to_string(),
+                                                                "example.com",
+                                                                443).unwrap();
+            assert_eq!(stream.read_timeout().unwrap(), None);
+            assert_eq!(stream.write_timeout().unwrap(), None);
+            server.join().unwrap();
+        }
+    }
+
+    mod connect_config {
+        use super::*;
+
+        #[test]
+        fn parses_a_number_of_seconds() {
+            let config: ConnectConfig = "3".parse().unwrap();
+            assert_eq!(config.connect_timeout, Duration::from_secs(3));
+        }
+
+        #[test]
+        fn rejects_non_numeric_input() { assert!("nope".parse::<ConnectConfig>().is_err()) }
+
+        #[test]
+        fn rejects_a_zero_second_timeout() {
+            assert!(ConnectConfig::validate("0".to_string()).is_err());
+        }
+
+        #[test]
+        fn connect_falls_back_to_a_working_address_after_a_refused_one() {
+            use std::net::TcpListener;
+
+            let refused_addr = {
+                // Bound, then immediately dropped, so the port is no longer listening and any
+                // connection to it is refused.
+                let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+                listener.local_addr().unwrap()
+            };
+
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let working_addr = listener.local_addr().unwrap();
+            let server = thread::spawn(move || listener.accept().unwrap());
+
+            let connect_config = ConnectConfig::new(Duration::from_secs(1));
+            let stream = connect_config.connect([refused_addr, working_addr].as_slice())
+                                        .unwrap();
+            assert_eq!(stream.peer_addr().unwrap(), working_addr);
+            server.join().unwrap();
+        }
+    }
+
+    mod reconnect_state {
+        use super::*;
+
+        fn no_jitter_policy() -> ReconnectPolicy {
+            ReconnectPolicy { base_delay:   Duration::from_secs(1),
+                              max_delay:    Duration::from_secs(8),
+                              max_retries:  None,
+                              jitter:       false, }
+        }
+
+        #[test]
+        fn backoff_doubles_on_each_consecutive_failure_up_to_the_max() {
+            let mut state = ReconnectState::new(no_jitter_policy());
+            assert_eq!(state.record_failure(), Some(Duration::from_secs(1)));
+            assert_eq!(state.record_failure(), Some(Duration::from_secs(2)));
+            assert_eq!(state.record_failure(), Some(Duration::from_secs(4)));
+            assert_eq!(state.record_failure(), Some(Duration::from_secs(8)));
+            // Capped at max_delay from here on.
+            assert_eq!(state.record_failure(), Some(Duration::from_secs(8)));
+        }
+
+        #[test]
+        fn success_resets_backoff_to_the_base_delay() {
+            let mut state = ReconnectState::new(no_jitter_policy());
+            state.record_failure();
+            state.record_failure();
+            state.record_success();
+            assert_eq!(state.state(), ConnectionState::Connected);
+            assert_eq!(state.record_failure(), Some(Duration::from_secs(1)));
+        }
+
+        #[test]
+        fn max_retries_exhausted_yields_none() {
+            let mut policy = no_jitter_policy();
+            policy.max_retries = Some(1);
+            let mut state = ReconnectState::new(policy);
+            assert_eq!(state.record_failure(), Some(Duration::from_secs(1)));
+            assert_eq!(state.record_failure(), None);
+        }
+    }
+
     mod auth_token {
         use super::*;
 
@@ -325,6 +1207,82 @@ mod tests {
             let local_addr = listen_addr.local_addr();
             assert_eq!(listen_addr, local_addr);
         }
+
+        #[test]
+        fn local_addr_for_gossip_listen_addr_works_for_unspecified_ipv6_address() {
+            let listen_addr =
+                GossipListenAddr(SocketAddr::V6(SocketAddrV6::new(Ipv6Addr::UNSPECIFIED,
+                                                                   GossipListenAddr::DEFAULT_PORT,
+                                                                   0,
+                                                                   0)));
+            let local_addr = listen_addr.local_addr();
+            assert!(local_addr.0.ip().is_loopback());
+        }
+
+        #[test]
+        fn local_only_picks_an_address_in_the_same_family() {
+            let v4_listen_addr = GossipListenAddr::default();
+            assert!(matches!(v4_listen_addr.local_only().0, SocketAddr::V4(_)));
+
+            let v6_listen_addr =
+                GossipListenAddr(SocketAddr::V6(SocketAddrV6::new(Ipv6Addr::UNSPECIFIED,
+                                                                   GossipListenAddr::DEFAULT_PORT,
+                                                                   0,
+                                                                   0)));
+            assert!(matches!(v6_listen_addr.local_only().0, SocketAddr::V6(_)));
+        }
+    }
+
+    mod listen_ctl_addr {
+        use super::*;
+
+        #[test]
+        fn parses_tcp_host_port() {
+            let addr: ListenCtlAddr = "127.0.0.1:9632".parse().unwrap();
+            assert_eq!(addr, ListenCtlAddr::new(Ipv4Addr::LOCALHOST, 9632));
+        }
+
+        #[test]
+        fn parses_unix_socket_path() {
+            let addr: ListenCtlAddr = "unix:/run/hab/ctl.sock".parse().unwrap();
+            assert_eq!(addr, ListenCtlAddr::Unix(PathBuf::from("/run/hab/ctl.sock")));
+        }
+
+        #[test]
+        fn ip_and_port_are_none_for_unix_socket() {
+            let addr: ListenCtlAddr = "unix:/run/hab/ctl.sock".parse().unwrap();
+            assert_eq!(addr.ip(), None);
+            assert_eq!(addr.port(), None);
+            assert_eq!(addr.as_tcp(), None);
+        }
+
+        #[test]
+        fn as_tcp_returns_the_address_for_a_tcp_listener() {
+            let addr: ListenCtlAddr = "127.0.0.1:9632".parse().unwrap();
+            assert_eq!(addr.as_tcp(), Some(&SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::LOCALHOST,
+                                                                             9632))));
+        }
+
+        mod env_config {
+            use super::*;
+            use habitat_core::env::Config as EnvConfig;
+
+            crate::locked_env_var!(HAB_LISTEN_CTL, lock_hab_listen_ctl);
+
+            #[test]
+            fn no_env_var_yields_default() {
+                let _envvar = lock_hab_listen_ctl();
+                assert_eq!(ListenCtlAddr::configured_value(), ListenCtlAddr::default());
+            }
+
+            #[test]
+            fn env_var_yields_parsed_value() {
+                let envvar = lock_hab_listen_ctl();
+                envvar.set("unix:/run/hab/ctl.sock");
+                assert_eq!(ListenCtlAddr::configured_value(),
+                           ListenCtlAddr::Unix(PathBuf::from("/run/hab/ctl.sock")));
+            }
+        }
     }
 
     mod env_config {